@@ -3,28 +3,29 @@
 //! Provides commands to set and clear the dock badge count for notifications.
 
 #[cfg(target_os = "macos")]
-use cocoa::appkit::NSApp;
+use objc2::MainThreadMarker;
 #[cfg(target_os = "macos")]
-use cocoa::base::nil;
+use objc2_app_kit::NSApplication;
 #[cfg(target_os = "macos")]
-use cocoa::foundation::NSString;
-#[cfg(target_os = "macos")]
-use objc::{msg_send, sel, sel_impl};
+use objc2_foundation::NSString;
 
 /// Set the dock badge to show a count.
 /// Pass 0 to clear the badge.
 #[tauri::command]
 pub fn set_dock_badge(count: u32) {
     #[cfg(target_os = "macos")]
-    unsafe {
-        let app = NSApp();
-        let dock_tile: cocoa::base::id = msg_send![app, dockTile];
-        let badge_label = if count > 0 {
-            NSString::alloc(nil).init_str(&count.to_string())
-        } else {
-            nil
+    {
+        let Some(mtm) = MainThreadMarker::new() else {
+            eprintln!("[dock] set_dock_badge must be called on the main thread");
+            return;
         };
-        let _: () = msg_send![dock_tile, setBadgeLabel: badge_label];
+
+        let app = NSApplication::sharedApplication(mtm);
+        let badge_label = (count > 0).then(|| NSString::from_str(&count.to_string()));
+        unsafe {
+            let dock_tile = app.dockTile();
+            dock_tile.setBadgeLabel(badge_label.as_deref());
+        }
     }
 
     #[cfg(not(target_os = "macos"))]
@@ -1,8 +1,10 @@
-//! Unlocks high refresh rate (120fps) rendering in WKWebView on macOS.
+//! Runtime-toggleable high refresh rate (120fps) unlock for WKWebView on macOS.
 //!
 //! By default, WKWebView caps requestAnimationFrame and rendering to 60fps,
 //! even on ProMotion displays that support 120Hz. This module uses private
-//! WebKit APIs to disable this limitation.
+//! WebKit APIs to disable this limitation, and exposes it as a Tauri
+//! command pair so the frontend can flip it at runtime and show an honest
+//! status instead of guessing.
 //!
 //! ## How it works
 //!
@@ -11,13 +13,16 @@
 //! to 60fps for power efficiency. This preference is exposed through WebKit's
 //! private `_experimentalFeatures` API on `WKPreferences`.
 //!
-//! We access this API to disable the preference, allowing the WebView to
-//! render at the display's native refresh rate (e.g., 120Hz on ProMotion).
+//! We access this API to enable or disable the preference, letting the
+//! WebView render at the display's native refresh rate (e.g., 120Hz on
+//! ProMotion) or restoring the default 60fps cap.
 //!
 //! ## Compatibility
 //!
 //! - **macOS**: 10.14.4+ (when `_experimentalFeatures` was introduced)
 //! - **Tested on**: macOS 15 (Sequoia)
+//! - **Other platforms**: `set_high_refresh_rate` is a no-op that returns
+//!   `Ok(())`, and `high_refresh_rate_status` reports `Unsupported`
 //!
 //! ## Caveats
 //!
@@ -26,133 +31,290 @@
 //! - **Future macOS versions**: Private APIs can change. The code gracefully
 //!   fails if the API is unavailable, so updates won't break the app.
 //! - **Battery life**: Higher frame rates use more power. This is why Apple
-//!   defaults to 60fps.
-
-use objc2::rc::Retained;
-use objc2::runtime::{AnyClass, AnyObject, Bool};
-use objc2::{class, msg_send};
-use objc2_foundation::{NSArray, NSString};
-
-/// Disables the 60fps frame rate lock on a WKWebView, enabling ProMotion 120fps.
-///
-/// Returns `Ok(())` if the preference was found and disabled, or an error
-/// describing why it couldn't be disabled (API unavailable, preference not found).
-///
-/// This function is safe to call even if the API changes - it will simply
-/// return an error and the app will continue to work at 60fps.
-pub fn unlock_high_refresh_rate(webview_ptr: *mut std::ffi::c_void) -> Result<(), String> {
-    if webview_ptr.is_null() {
-        return Err("WebView pointer is null".to_string());
+//!   defaults to 60fps, and why the frontend lets the user turn it back off.
+
+use serde::Serialize;
+
+/// Which feature list the 60fps preference was found in. It has moved
+/// between internal debug and experimental features across macOS versions.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FeatureList {
+    InternalDebug,
+    Experimental,
+}
+
+/// Result of `high_refresh_rate_status`.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum HighRefreshRateStatus {
+    /// The private API is unavailable (non-macOS, or the preference moved again).
+    Unsupported,
+    /// The preference was found; `enabled` is whether high refresh rate is
+    /// currently unlocked (i.e. the 60fps cap is disabled).
+    Available {
+        feature_list: FeatureList,
+        enabled: bool,
+    },
+}
+
+/// Enables or disables ProMotion's 120fps rendering for the main window's
+/// WKWebView. No-op on platforms without the private API.
+#[tauri::command]
+pub fn set_high_refresh_rate(window: tauri::WebviewWindow, enabled: bool) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let (tx, rx) = std::sync::mpsc::channel();
+        window
+            .with_webview(move |wv| {
+                let _ = tx.send(macos::set_enabled(wv.inner(), enabled));
+            })
+            .map_err(|e| e.to_string())?;
+        rx.recv().map_err(|e| e.to_string())?
     }
 
-    unsafe {
-        let webview = webview_ptr as *mut AnyObject;
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (window, enabled);
+        Ok(())
+    }
+}
 
-        // Get WKWebViewConfiguration -> WKPreferences
-        let config: *mut AnyObject = msg_send![webview, configuration];
-        if config.is_null() {
-            return Err("Failed to get WKWebViewConfiguration".to_string());
-        }
+/// Reports whether high refresh rate is currently unlocked for the main
+/// window's WKWebView, and which feature list the preference lives in.
+#[tauri::command]
+pub fn high_refresh_rate_status(
+    window: tauri::WebviewWindow,
+) -> Result<HighRefreshRateStatus, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let (tx, rx) = std::sync::mpsc::channel();
+        window
+            .with_webview(move |wv| {
+                let _ = tx.send(
+                    macos::status(wv.inner()).unwrap_or(HighRefreshRateStatus::Unsupported),
+                );
+            })
+            .map_err(|e| e.to_string())?;
+        rx.recv().map_err(|e| e.to_string())
+    }
 
-        let preferences: *mut AnyObject = msg_send![config, preferences];
-        if preferences.is_null() {
-            return Err("Failed to get WKPreferences".to_string());
-        }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = window;
+        Ok(HighRefreshRateStatus::Unsupported)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{FeatureList, HighRefreshRateStatus};
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyClass, AnyObject, Bool};
+    use objc2::{class, msg_send};
+    use objc2_foundation::{NSArray, NSString};
 
-        let wk_preferences_class = class!(WKPreferences);
+    /// Disables (or re-enables) the 60fps frame rate lock on a WKWebView.
+    ///
+    /// This function is safe to call even if the API changes - it will
+    /// simply return an error and the app will continue to work at 60fps.
+    pub(super) fn set_enabled(
+        webview_ptr: *mut std::ffi::c_void,
+        enabled: bool,
+    ) -> Result<(), String> {
+        let (wk_preferences_class, preferences) = webview_preferences(webview_ptr)?;
 
-        // The 60fps preference moved between internal debug features and
-        // experimental features across macOS versions. Check both.
-        if let Some(result) = try_disable_in_features(
-            wk_preferences_class,
-            preferences,
-            FeatureType::InternalDebug,
-        ) {
-            return result;
+        unsafe {
+            // The 60fps preference moved between internal debug and
+            // experimental features across macOS versions. Check both.
+            if let Some(result) = try_set_in_features(
+                wk_preferences_class,
+                preferences,
+                FeatureType::InternalDebug,
+                enabled,
+            ) {
+                return result;
+            }
+
+            if let Some(result) = try_set_in_features(
+                wk_preferences_class,
+                preferences,
+                FeatureType::Experimental,
+                enabled,
+            ) {
+                return result;
+            }
         }
 
-        if let Some(result) =
-            try_disable_in_features(wk_preferences_class, preferences, FeatureType::Experimental)
-        {
-            return result;
+        Err("60fps preference not found in internal debug or experimental features".to_string())
+    }
+
+    /// Reports which feature list the 60fps preference lives in and whether
+    /// it's currently disabled.
+    pub(super) fn status(webview_ptr: *mut std::ffi::c_void) -> Result<HighRefreshRateStatus, String> {
+        let (wk_preferences_class, preferences) = webview_preferences(webview_ptr)?;
+
+        unsafe {
+            if let Some(status) =
+                try_get_status_in_features(wk_preferences_class, preferences, FeatureType::InternalDebug)
+            {
+                return Ok(status);
+            }
+
+            if let Some(status) =
+                try_get_status_in_features(wk_preferences_class, preferences, FeatureType::Experimental)
+            {
+                return Ok(status);
+            }
         }
 
         Err("60fps preference not found in internal debug or experimental features".to_string())
     }
-}
 
-#[derive(Clone, Copy)]
-enum FeatureType {
-    InternalDebug,
-    Experimental,
-}
+    fn webview_preferences(
+        webview_ptr: *mut std::ffi::c_void,
+    ) -> Result<(&'static AnyClass, *mut AnyObject), String> {
+        if webview_ptr.is_null() {
+            return Err("WebView pointer is null".to_string());
+        }
 
-/// Attempts to find and disable the 60fps preference in the given feature list.
-/// Returns `Some(Ok(()))` if found and disabled, `Some(Err(...))` if found but
-/// failed to disable, or `None` if not found in this feature list.
-unsafe fn try_disable_in_features(
-    wk_preferences_class: &AnyClass,
-    preferences: *mut AnyObject,
-    feature_type: FeatureType,
-) -> Option<Result<(), String>> {
-    let features: Option<Retained<NSArray<AnyObject>>> = match feature_type {
-        FeatureType::InternalDebug => msg_send![wk_preferences_class, _internalDebugFeatures],
-        FeatureType::Experimental => msg_send![wk_preferences_class, _experimentalFeatures],
-    };
-
-    let features = features?;
-    let count: usize = msg_send![&*features, count];
-
-    for i in 0..count {
-        let feature: *mut AnyObject = msg_send![&*features, objectAtIndex: i];
-        if feature.is_null() {
-            continue;
+        unsafe {
+            let webview = webview_ptr as *mut AnyObject;
+
+            // Get WKWebViewConfiguration -> WKPreferences
+            let config: *mut AnyObject = msg_send![webview, configuration];
+            if config.is_null() {
+                return Err("Failed to get WKWebViewConfiguration".to_string());
+            }
+
+            let preferences: *mut AnyObject = msg_send![config, preferences];
+            if preferences.is_null() {
+                return Err("Failed to get WKPreferences".to_string());
+            }
+
+            Ok((class!(WKPreferences), preferences))
         }
+    }
+
+    #[derive(Clone, Copy)]
+    enum FeatureType {
+        InternalDebug,
+        Experimental,
+    }
 
-        let key: Option<Retained<NSString>> = msg_send![feature, key];
-        let key = match key {
-            Some(k) => k,
-            None => continue,
+    impl FeatureType {
+        fn as_feature_list(self) -> FeatureList {
+            match self {
+                FeatureType::InternalDebug => FeatureList::InternalDebug,
+                FeatureType::Experimental => FeatureList::Experimental,
+            }
+        }
+    }
+
+    /// Scans the given feature list for the 60fps preference and returns the
+    /// raw feature object, or `None` if this list doesn't have it.
+    unsafe fn find_60fps_feature(
+        wk_preferences_class: &AnyClass,
+        feature_type: FeatureType,
+    ) -> Option<*mut AnyObject> {
+        let features: Option<Retained<NSArray<AnyObject>>> = match feature_type {
+            FeatureType::InternalDebug => msg_send![wk_preferences_class, _internalDebugFeatures],
+            FeatureType::Experimental => msg_send![wk_preferences_class, _experimentalFeatures],
         };
 
-        let key_str = key.to_string();
-
-        // Match the preference by key name
-        // Known names: "PreferPageRenderingUpdatesNear60FPSEnabled"
-        if key_str.contains("PreferPageRenderingUpdatesNear60FPS")
-            || key_str.contains("60FPS")
-            || key_str.contains("60fps")
-        {
-            // Disable the preference based on feature type
-            match feature_type {
-                FeatureType::InternalDebug => {
-                    let _: () = msg_send![
-                        preferences,
-                        _setEnabled: Bool::NO,
-                        forInternalDebugFeature: feature
-                    ];
-                }
-                FeatureType::Experimental => {
-                    let _: () = msg_send![
-                        preferences,
-                        _setEnabled: Bool::NO,
-                        forExperimentalFeature: feature
-                    ];
-                }
+        let features = features?;
+        let count: usize = msg_send![&*features, count];
+
+        for i in 0..count {
+            let feature: *mut AnyObject = msg_send![&*features, objectAtIndex: i];
+            if feature.is_null() {
+                continue;
             }
 
-            let type_name = match feature_type {
-                FeatureType::InternalDebug => "internal debug",
-                FeatureType::Experimental => "experimental",
+            let key: Option<Retained<NSString>> = msg_send![feature, key];
+            let key = match key {
+                Some(k) => k,
+                None => continue,
             };
-            println!(
-                "[high_refresh_rate] Disabled '{}' ({} feature) - 120fps unlocked",
-                key_str, type_name
-            );
 
-            return Some(Ok(()));
+            let key_str = key.to_string();
+
+            // Known names: "PreferPageRenderingUpdatesNear60FPSEnabled"
+            if key_str.contains("PreferPageRenderingUpdatesNear60FPS")
+                || key_str.contains("60FPS")
+                || key_str.contains("60fps")
+            {
+                return Some(feature);
+            }
+        }
+
+        None
+    }
+
+    /// Attempts to find the 60fps preference in the given feature list and
+    /// set it so that `enabled` reflects whether high refresh rate is
+    /// unlocked. Returns `Some(Ok(()))` if found and set, `Some(Err(...))`
+    /// if found but failed to set, or `None` if not found in this list.
+    unsafe fn try_set_in_features(
+        wk_preferences_class: &AnyClass,
+        preferences: *mut AnyObject,
+        feature_type: FeatureType,
+        enabled: bool,
+    ) -> Option<Result<(), String>> {
+        let feature = find_60fps_feature(wk_preferences_class, feature_type)?;
+
+        // The preference itself means "cap rendering at 60fps", so unlocking
+        // high refresh rate means disabling it.
+        let native_enabled = Bool::new(!enabled);
+        match feature_type {
+            FeatureType::InternalDebug => {
+                let _: () = msg_send![
+                    preferences,
+                    _setEnabled: native_enabled,
+                    forInternalDebugFeature: feature
+                ];
+            }
+            FeatureType::Experimental => {
+                let _: () = msg_send![
+                    preferences,
+                    _setEnabled: native_enabled,
+                    forExperimentalFeature: feature
+                ];
+            }
         }
+
+        println!(
+            "[high_refresh_rate] {} ({:?} feature) - high refresh rate {}",
+            if enabled { "Disabled 60fps cap" } else { "Restored 60fps cap" },
+            feature_type.as_feature_list(),
+            if enabled { "unlocked" } else { "locked to 60fps" }
+        );
+
+        Some(Ok(()))
     }
 
-    None
+    /// Attempts to find the 60fps preference in the given feature list and
+    /// report whether it's currently disabled. Returns `None` if not found
+    /// in this list.
+    unsafe fn try_get_status_in_features(
+        wk_preferences_class: &AnyClass,
+        preferences: *mut AnyObject,
+        feature_type: FeatureType,
+    ) -> Option<HighRefreshRateStatus> {
+        let feature = find_60fps_feature(wk_preferences_class, feature_type)?;
+
+        let native_enabled: Bool = match feature_type {
+            FeatureType::InternalDebug => {
+                msg_send![preferences, _isEnabledForInternalDebugFeature: feature]
+            }
+            FeatureType::Experimental => {
+                msg_send![preferences, _isEnabledForExperimentalFeature: feature]
+            }
+        };
+
+        Some(HighRefreshRateStatus::Available {
+            feature_list: feature_type.as_feature_list(),
+            enabled: !native_enabled.as_bool(),
+        })
+    }
 }
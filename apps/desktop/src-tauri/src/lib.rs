@@ -1,25 +1,19 @@
 use tauri::{Manager, TitleBarStyle, WebviewUrl, WebviewWindowBuilder};
 
 mod dock;
+mod high_refresh_rate;
 mod markdown;
+mod protocol;
+mod settings;
 mod sidecar;
+use settings::Settings;
 use sidecar::SidecarState;
 
-#[cfg(target_os = "macos")]
-mod high_refresh_rate;
-
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
-/// Get the port the embedded server is running on.
-/// Returns None if the server hasn't started yet or failed to start.
-#[tauri::command]
-async fn get_server_port(state: tauri::State<'_, SidecarState>) -> Result<Option<u16>, String> {
-    Ok(state.get_port().await)
-}
-
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -29,6 +23,7 @@ pub fn run() {
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_opener::init())
         .manage(SidecarState::new())
+        .register_asynchronous_uri_scheme_protocol(protocol::SCHEME, protocol::handle)
         .setup(|app| {
             // Start sidecar on app launch
             let app_handle = app.handle().clone();
@@ -39,9 +34,11 @@ pub fn run() {
                 }
             });
 
+            let settings = Settings::load(app.handle());
+
             let win_builder = WebviewWindowBuilder::new(app, "main", WebviewUrl::default())
                 .title("Sandcastle")
-                .inner_size(1440.0, 900.0)
+                .inner_size(settings.window_width, settings.window_height)
                 .min_inner_size(800.0, 600.0)
                 .resizable(true)
                 .fullscreen(false);
@@ -55,26 +52,26 @@ pub fn run() {
 
             #[cfg(target_os = "macos")]
             {
-                use cocoa::appkit::{NSColor, NSWindow};
-                use cocoa::base::{id, nil};
+                use objc2_app_kit::{NSColor, NSWindow};
 
-                let ns_window = window.ns_window().unwrap() as id;
+                // `ns_window()` returns `*mut c_void` on recent Tauri versions.
+                let ns_window = window.ns_window().unwrap() as *const NSWindow;
                 unsafe {
                     // Use opaque background for better compositing performance
-                    let bg_color = NSColor::colorWithRed_green_blue_alpha_(
-                        nil, 0.08, 0.08, 0.08, 1.0, // Fully opaque
-                    );
-                    ns_window.setBackgroundColor_(bg_color);
+                    let bg_color = NSColor::colorWithRed_green_blue_alpha(0.08, 0.08, 0.08, 1.0); // Fully opaque
+                    (*ns_window).setBackgroundColor(Some(&bg_color));
                 }
 
-                // Unlock 120fps rendering on ProMotion displays
+                // Unlock 120fps rendering on ProMotion displays, unless the
+                // user has turned it off to save battery.
                 // Uses private WebKit APIs - for direct distribution only, not App Store
-                let _ = window.with_webview(|wv| {
-                    if let Err(e) = high_refresh_rate::unlock_high_refresh_rate(wv.inner()) {
+                if settings.high_refresh_rate_enabled {
+                    if let Err(e) = high_refresh_rate::set_high_refresh_rate(window.clone(), true)
+                    {
                         // Non-fatal: app works fine at 60fps if this fails
                         eprintln!("[high_refresh_rate] {}", e);
                     }
-                });
+                }
             }
 
             Ok(())
@@ -93,10 +90,13 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             greet,
-            get_server_port,
             markdown::parse_markdown_command,
             dock::set_dock_badge,
-            dock::clear_dock_badge
+            dock::clear_dock_badge,
+            settings::get_settings,
+            settings::set_settings,
+            high_refresh_rate::set_high_refresh_rate,
+            high_refresh_rate::high_refresh_rate_status
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
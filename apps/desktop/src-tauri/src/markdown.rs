@@ -1,7 +1,39 @@
-use comrak::{markdown_to_html, Options};
+use comrak::plugins::syntect::SyntectAdapter;
+use comrak::{markdown_to_html_with_plugins, ComrakPlugins, Options};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use syntect::highlighting::ThemeSet;
+
+/// Default syntax theme, matching the app's opaque dark window chrome.
+const DEFAULT_THEME: &str = "base16-ocean.dark";
+
+/// `SyntaxSet`/`ThemeSet` are expensive to load, so compiled adapters are
+/// cached per theme name instead of rebuilt on every call.
+static ADAPTERS: LazyLock<Mutex<HashMap<String, SyntectAdapter>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Theme names syntect actually bundles. `SyntectAdapter` indexes its
+/// `ThemeSet` by name and panics on an unrecognized one, so callers must be
+/// validated against this before a theme is ever handed to `SyntectAdapter::new`.
+static KNOWN_THEMES: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+/// Parse markdown input and return HTML, syntax-highlighting fenced code
+/// blocks server-side with `theme` (a syntect theme name; defaults to a dark
+/// theme matching the app's window chrome, and falls back to that default if
+/// `theme` isn't one of syntect's bundled themes). Fences with an
+/// unrecognized or missing language fall back to plain escaped `<pre><code>`.
+pub fn parse_markdown(input: &str, theme: Option<&str>) -> String {
+    let requested = theme.unwrap_or(DEFAULT_THEME);
+    let theme = if KNOWN_THEMES.themes.contains_key(requested) {
+        requested
+    } else {
+        eprintln!(
+            "[markdown] Unknown theme '{}', falling back to '{}'",
+            requested, DEFAULT_THEME
+        );
+        DEFAULT_THEME
+    };
 
-/// Parse markdown input and return HTML.
-pub fn parse_markdown(input: &str) -> String {
     let mut options = Options::default();
 
     // GFM extensions
@@ -13,13 +45,27 @@ pub fn parse_markdown(input: &str) -> String {
     // Rendering options
     options.render.unsafe_ = true; // Allow raw HTML pass-through
 
-    markdown_to_html(input, &options)
+    let mut adapters = ADAPTERS.lock().unwrap();
+    let adapter = adapters
+        .entry(theme.to_string())
+        .or_insert_with(|| SyntectAdapter::new(Some(theme)));
+
+    let mut plugins = ComrakPlugins::default();
+    plugins.render.codefence_syntax_highlighter = Some(adapter);
+
+    markdown_to_html_with_plugins(input, &options, &plugins)
 }
 
 /// Tauri command to parse markdown from the frontend.
+///
+/// `theme` selects the syntect theme used for fenced code blocks; omit it to
+/// get the default dark theme.
 #[tauri::command]
-pub async fn parse_markdown_command(markdown: String) -> Result<String, String> {
-    Ok(parse_markdown(&markdown))
+pub async fn parse_markdown_command(
+    markdown: String,
+    theme: Option<String>,
+) -> Result<String, String> {
+    Ok(parse_markdown(&markdown, theme.as_deref()))
 }
 
 #[cfg(test)]
@@ -29,7 +75,7 @@ mod tests {
     #[test]
     fn test_basic_markdown() {
         let input = "# Hello\n\nThis is **bold** and *italic*.";
-        let html = parse_markdown(input);
+        let html = parse_markdown(input, None);
         assert!(html.contains("<h1>"));
         assert!(html.contains("<strong>bold</strong>"));
         assert!(html.contains("<em>italic</em>"));
@@ -38,14 +84,14 @@ mod tests {
     #[test]
     fn test_strikethrough() {
         let input = "~~deleted~~";
-        let html = parse_markdown(input);
+        let html = parse_markdown(input, None);
         assert!(html.contains("<del>deleted</del>"));
     }
 
     #[test]
     fn test_table() {
         let input = "| A | B |\n|---|---|\n| 1 | 2 |";
-        let html = parse_markdown(input);
+        let html = parse_markdown(input, None);
         assert!(html.contains("<table>"));
         assert!(html.contains("<th>"));
         assert!(html.contains("<td>"));
@@ -54,28 +100,51 @@ mod tests {
     #[test]
     fn test_tasklist() {
         let input = "- [x] Done\n- [ ] Todo";
-        let html = parse_markdown(input);
+        let html = parse_markdown(input, None);
         assert!(html.contains("checked"));
     }
 
     #[test]
     fn test_autolink() {
         let input = "Visit https://example.com for more.";
-        let html = parse_markdown(input);
+        let html = parse_markdown(input, None);
         assert!(html.contains("<a href=\"https://example.com\">"));
     }
 
     #[test]
-    fn test_code_block() {
+    fn test_code_block_without_language_falls_back_to_plain() {
+        let input = "```\nfn main() {}\n```";
+        let html = parse_markdown(input, None);
+        assert!(html.contains("<pre><code>"));
+        assert!(!html.contains("style=\"color:"));
+    }
+
+    #[test]
+    fn test_code_block_with_unknown_language_falls_back_to_plain() {
+        let input = "```not-a-real-language\nfn main() {}\n```";
+        let html = parse_markdown(input, None);
+        assert!(html.contains("<pre><code>"));
+        assert!(!html.contains("style=\"color:"));
+    }
+
+    #[test]
+    fn test_code_block_with_rust_is_syntax_highlighted() {
+        let input = "```rust\nfn main() {}\n```";
+        let html = parse_markdown(input, None);
+        assert!(html.contains("<pre style="));
+        assert!(html.contains("<span style=\"color:"));
+    }
+
+    #[test]
+    fn test_unknown_theme_falls_back_to_default_without_panicking() {
         let input = "```rust\nfn main() {}\n```";
-        let html = parse_markdown(input);
-        assert!(html.contains("<pre>"));
-        assert!(html.contains("<code"));
+        let html = parse_markdown(input, Some("not-a-real-theme"));
+        assert!(html.contains("<span style=\"color:"));
     }
 
     #[test]
     fn test_empty_input() {
-        let html = parse_markdown("");
+        let html = parse_markdown("", None);
         assert_eq!(html, "");
     }
 }
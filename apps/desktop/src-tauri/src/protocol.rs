@@ -0,0 +1,90 @@
+//! `sandcastle://` custom URI scheme protocol.
+//!
+//! The frontend used to talk to the sidecar over a real TCP listener on
+//! `localhost`, which meant any other process on the machine could connect
+//! to it too. This registers an asynchronous custom scheme protocol instead:
+//! requests made to `sandcastle://` are forwarded to the sidecar's loopback
+//! port as an implementation detail, and the response is resolved through
+//! the responder. `Range` requests are forwarded as-is, so the sidecar only
+//! ever sends back the requested slice of a large asset instead of the
+//! handler buffering the whole file.
+
+use std::sync::LazyLock;
+use tauri::http::header::{HOST, ORIGIN, REFERER};
+use tauri::http::{Request, Response, StatusCode};
+use tauri::{AppHandle, Manager, UriSchemeContext, UriSchemeResponder};
+
+use crate::sidecar::SidecarState;
+
+/// Scheme name registered with the `tauri::Builder`.
+pub const SCHEME: &str = "sandcastle";
+
+/// Reused across requests so connection pooling isn't rebuilt on every
+/// single asset fetch.
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
+/// Handles a request made on the `sandcastle://` scheme by forwarding it to
+/// the sidecar and resolving `responder` with whatever it sends back.
+pub fn handle(ctx: UriSchemeContext<'_, tauri::Wry>, request: Request<Vec<u8>>, responder: UriSchemeResponder) {
+    let app = ctx.app_handle().clone();
+
+    tauri::async_runtime::spawn(async move {
+        let response = forward(&app, request).await.unwrap_or_else(|e| {
+            eprintln!("[protocol] {}", e);
+            Response::builder()
+                .status(StatusCode::BAD_GATEWAY)
+                .body(Vec::new())
+                .unwrap()
+        });
+
+        responder.respond(response);
+    });
+}
+
+/// Forwards `request` to the sidecar over its loopback port and returns the
+/// upstream response, preserving status, headers (including `Content-Range`
+/// on ranged responses) and body.
+async fn forward(app: &AppHandle, request: Request<Vec<u8>>) -> Result<Response<Vec<u8>>, String> {
+    let state = app.state::<SidecarState>();
+    let port = state
+        .get_port()
+        .await
+        .ok_or_else(|| "Sidecar is not running".to_string())?;
+
+    let path_and_query = request.uri().path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    let url = format!("http://127.0.0.1:{port}{path_and_query}");
+
+    let mut upstream_request = CLIENT.request(request.method().clone(), &url);
+
+    for (name, value) in request.headers() {
+        // `Host` on the incoming request is the webview's synthetic
+        // custom-scheme authority (e.g. `sandcastle.localhost`), not the
+        // sidecar's; let reqwest derive the real one from `url` instead.
+        // `Origin`/`Referer` leak that same synthetic authority and the
+        // sidecar has no use for them.
+        if name == HOST || name == ORIGIN || name == REFERER {
+            continue;
+        }
+        upstream_request = upstream_request.header(name, value);
+    }
+
+    if !request.body().is_empty() {
+        upstream_request = upstream_request.body(request.body().clone());
+    }
+
+    let upstream = upstream_request.send().await.map_err(|e| e.to_string())?;
+
+    let status = upstream.status();
+    let headers = upstream.headers().clone();
+    // When the request carried a `Range` header the sidecar already replies
+    // with 206 and only the requested slice, so this never holds more than
+    // that slice in memory even for large media assets.
+    let body = upstream.bytes().await.map_err(|e| e.to_string())?.to_vec();
+
+    let mut response = Response::builder().status(status);
+    for (name, value) in headers.iter() {
+        response = response.header(name, value);
+    }
+
+    response.body(body).map_err(|e| e.to_string())
+}
@@ -0,0 +1,125 @@
+//! Persistent, crash-safe user settings.
+//!
+//! Settings are serialized to JSON in the app config dir. Writes go through
+//! a temp-file + `fsync` + atomic rename so a power loss mid-save can't
+//! corrupt the live file, and the previous contents are kept as
+//! `settings.json.bak` so a corrupted or truncated `settings.json` can still
+//! be recovered on the next launch.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+const SETTINGS_FILE: &str = "settings.json";
+const SETTINGS_BACKUP_FILE: &str = "settings.json.bak";
+const SETTINGS_TMP_FILE: &str = "settings.json.tmp";
+
+/// Serializes concurrent `save` calls so two saves racing on Tauri's
+/// blocking thread-pool (e.g. a debounced window-resize save racing a
+/// user's explicit toggle save) can't interleave their writes into one
+/// corrupted `settings.json.tmp`.
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub high_refresh_rate_enabled: bool,
+    pub window_width: f64,
+    pub window_height: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            high_refresh_rate_enabled: true,
+            window_width: 1440.0,
+            window_height: 900.0,
+        }
+    }
+}
+
+impl Settings {
+    /// Load settings from disk, falling back to the backup copy if the live
+    /// file is missing or corrupted, and to defaults if neither is usable.
+    pub fn load(app: &AppHandle) -> Self {
+        let dir = match Self::config_dir(app) {
+            Ok(dir) => dir,
+            Err(e) => {
+                eprintln!("[settings] Failed to resolve config dir: {}", e);
+                return Self::default();
+            }
+        };
+
+        Self::read(&dir.join(SETTINGS_FILE)).unwrap_or_else(|e| {
+            eprintln!(
+                "[settings] Failed to read {} ({}), trying backup",
+                SETTINGS_FILE, e
+            );
+            Self::read(&dir.join(SETTINGS_BACKUP_FILE)).unwrap_or_else(|e| {
+                eprintln!(
+                    "[settings] Failed to read {} ({}), using defaults",
+                    SETTINGS_BACKUP_FILE, e
+                );
+                Self::default()
+            })
+        })
+    }
+
+    /// Persist settings, keeping the previous contents as a backup.
+    ///
+    /// The write goes to a temp file in the same directory, which is
+    /// `fsync`ed and then atomically renamed over the live file, so a crash
+    /// mid-write can never leave `settings.json` truncated or half-written.
+    pub fn save(&self, app: &AppHandle) -> Result<(), String> {
+        // Hold the lock for the whole temp-write + backup + rename sequence
+        // so concurrent saves serialize instead of interleaving.
+        let _guard = SAVE_LOCK.lock().map_err(|e| e.to_string())?;
+
+        let dir = Self::config_dir(app)?;
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+        let path = dir.join(SETTINGS_FILE);
+        let backup_path = dir.join(SETTINGS_BACKUP_FILE);
+        let tmp_path = dir.join(SETTINGS_TMP_FILE);
+
+        let json = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
+
+        {
+            let mut tmp_file = fs::File::create(&tmp_path).map_err(|e| e.to_string())?;
+            tmp_file.write_all(&json).map_err(|e| e.to_string())?;
+            tmp_file.sync_all().map_err(|e| e.to_string())?;
+        }
+
+        if path.exists() {
+            fs::copy(&path, &backup_path).map_err(|e| e.to_string())?;
+        }
+
+        fs::rename(&tmp_path, &path).map_err(|e| e.to_string())?;
+
+        Ok(())
+    }
+
+    fn read(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&contents).map_err(|e| e.to_string())
+    }
+
+    fn config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+        app.path().app_config_dir().map_err(|e| e.to_string())
+    }
+}
+
+/// Read the current settings from disk.
+#[tauri::command]
+pub fn get_settings(app: AppHandle) -> Settings {
+    Settings::load(&app)
+}
+
+/// Persist new settings, replacing whatever was saved before.
+#[tauri::command]
+pub fn set_settings(app: AppHandle, settings: Settings) -> Result<(), String> {
+    settings.save(&app)
+}
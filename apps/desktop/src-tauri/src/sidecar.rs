@@ -1,17 +1,30 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tauri::AppHandle;
-use tauri::Manager;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_shell::process::CommandChild;
 use tauri_plugin_shell::ShellExt;
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
 
 const HEALTH_CHECK_MAX_ATTEMPTS: u32 = 50;
 const HEALTH_CHECK_DELAY_MS: u64 = 100;
 const PORT_PARSE_TIMEOUT_MS: u64 = 10000;
 
+const RESTART_INITIAL_BACKOFF_MS: u64 = 100;
+const RESTART_MAX_BACKOFF_MS: u64 = 5000;
+const RESTART_MAX_CONSECUTIVE_FAILURES: u32 = 5;
+
+/// Emitted on the frontend as the sidecar's lifecycle changes, with the
+/// status string as payload: `"starting"` / `"ready"` / `"crashed"` (a
+/// transient crash, about to be retried) / `"failed"` (gave up after
+/// `RESTART_MAX_CONSECUTIVE_FAILURES` restart attempts - this is terminal,
+/// the sidecar will not come back on its own).
+const SIDECAR_STATUS_EVENT: &str = "sidecar://status";
+
 pub struct SidecarState {
     child: Arc<Mutex<Option<CommandChild>>>,
     port: Arc<Mutex<Option<u16>>>,
+    shutting_down: Arc<AtomicBool>,
 }
 
 impl Default for SidecarState {
@@ -25,6 +38,7 @@ impl SidecarState {
         Self {
             child: Arc::new(Mutex::new(None)),
             port: Arc::new(Mutex::new(None)),
+            shutting_down: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -33,7 +47,9 @@ impl SidecarState {
         *self.port.lock().await
     }
 
-    /// Start the Bun sidecar with the bundled server
+    /// Start the Bun sidecar with the bundled server, and keep it supervised
+    /// for the rest of the app's lifetime: if it ever crashes, it's
+    /// automatically restarted with capped exponential backoff.
     pub async fn start(&self, app: &AppHandle) -> Result<u16, String> {
         let mut child_guard = self.child.lock().await;
 
@@ -46,6 +62,55 @@ impl SidecarState {
                 .ok_or_else(|| "Server running but port unknown".to_string());
         }
 
+        self.shutting_down.store(false, Ordering::SeqCst);
+
+        let (port, terminated_rx) = Self::spawn(app, &mut child_guard, &self.port).await?;
+        drop(child_guard);
+
+        let app_handle = app.clone();
+        let child = self.child.clone();
+        let port_state = self.port.clone();
+        let shutting_down = self.shutting_down.clone();
+        tauri::async_runtime::spawn(Self::supervise(
+            app_handle,
+            child,
+            port_state,
+            shutting_down,
+            terminated_rx,
+        ));
+
+        Ok(port)
+    }
+
+    /// Stop the sidecar gracefully
+    pub async fn stop(&self) -> Result<(), String> {
+        // Tell the supervisor this is a deliberate stop, not a crash, so it
+        // doesn't try to restart the server we're about to kill.
+        self.shutting_down.store(true, Ordering::SeqCst);
+
+        let mut child_guard = self.child.lock().await;
+
+        if let Some(child) = child_guard.take() {
+            println!("[sidecar] Stopping server...");
+            // Send kill signal - the server handles SIGTERM gracefully via Effect finalizers
+            child.kill().map_err(|e| e.to_string())?;
+            *self.port.lock().await = None;
+            println!("[sidecar] Server stopped");
+        }
+
+        Ok(())
+    }
+
+    /// Spawns the sidecar, waits for it to report its port and become ready,
+    /// and returns that port along with a oneshot that resolves when this
+    /// particular instance terminates.
+    async fn spawn(
+        app: &AppHandle,
+        child_guard: &mut Option<CommandChild>,
+        port_state: &Arc<Mutex<Option<u16>>>,
+    ) -> Result<(u16, oneshot::Receiver<()>), String> {
+        Self::emit_status(app, "starting");
+
         // Get path to bundled server.js from resources
         let resource_path = app
             .path()
@@ -78,10 +143,15 @@ impl SidecarState {
         *child_guard = Some(child);
 
         // Channel to receive the parsed port
-        let (port_tx, port_rx) = tokio::sync::oneshot::channel::<u16>();
+        let (port_tx, port_rx) = oneshot::channel::<u16>();
         let port_tx = Arc::new(Mutex::new(Some(port_tx)));
 
-        // Spawn task to read sidecar output and parse the port
+        // Channel that resolves once this sidecar instance terminates, so
+        // the supervisor knows to restart it.
+        let (terminated_tx, terminated_rx) = oneshot::channel::<()>();
+
+        // Spawn task to read sidecar output, parse the port, and notify the
+        // supervisor when the process terminates.
         let port_tx_clone = port_tx.clone();
         tauri::async_runtime::spawn(async move {
             use tauri_plugin_shell::process::CommandEvent;
@@ -114,6 +184,8 @@ impl SidecarState {
                     _ => {}
                 }
             }
+
+            let _ = terminated_tx.send(());
         });
 
         // Wait for port with timeout
@@ -126,28 +198,91 @@ impl SidecarState {
         .map_err(|_| "Failed to receive port from server")?;
 
         // Store the port
-        *self.port.lock().await = Some(port);
+        *port_state.lock().await = Some(port);
 
         // Wait for server to be ready (poll health endpoint)
         Self::wait_for_ready(port).await?;
 
         println!("[sidecar] Server started successfully on port {}", port);
-        Ok(port)
+        Self::emit_status(app, "ready");
+        Ok((port, terminated_rx))
     }
 
-    /// Stop the sidecar gracefully
-    pub async fn stop(&self) -> Result<(), String> {
-        let mut child_guard = self.child.lock().await;
+    /// Watches for the sidecar to terminate and, unless we're deliberately
+    /// shutting down, restarts it with capped exponential backoff. Gives up
+    /// after `RESTART_MAX_CONSECUTIVE_FAILURES` failed restart attempts.
+    async fn supervise(
+        app: AppHandle,
+        child: Arc<Mutex<Option<CommandChild>>>,
+        port_state: Arc<Mutex<Option<u16>>>,
+        shutting_down: Arc<AtomicBool>,
+        mut terminated_rx: oneshot::Receiver<()>,
+    ) {
+        let mut backoff_ms = RESTART_INITIAL_BACKOFF_MS;
+        let mut consecutive_failures = 0u32;
 
-        if let Some(child) = child_guard.take() {
-            println!("[sidecar] Stopping server...");
-            // Send kill signal - the server handles SIGTERM gracefully via Effect finalizers
-            child.kill().map_err(|e| e.to_string())?;
-            *self.port.lock().await = None;
-            println!("[sidecar] Server stopped");
-        }
+        loop {
+            // Wait for the current sidecar instance to die.
+            let _ = terminated_rx.await;
 
-        Ok(())
+            if shutting_down.load(Ordering::SeqCst) {
+                return;
+            }
+
+            *port_state.lock().await = None;
+            Self::emit_status(&app, "crashed");
+
+            println!(
+                "[sidecar] Server crashed, restarting in {}ms...",
+                backoff_ms
+            );
+            tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+
+            // A stop could have been requested while we were asleep; don't
+            // spawn a replacement nothing will track or kill.
+            if shutting_down.load(Ordering::SeqCst) {
+                return;
+            }
+
+            let mut child_guard = child.lock().await;
+            *child_guard = None;
+
+            if shutting_down.load(Ordering::SeqCst) {
+                return;
+            }
+
+            match Self::spawn(&app, &mut child_guard, &port_state).await {
+                Ok((_, next_terminated_rx)) => {
+                    consecutive_failures = 0;
+                    backoff_ms = RESTART_INITIAL_BACKOFF_MS;
+                    terminated_rx = next_terminated_rx;
+                }
+                Err(e) => {
+                    consecutive_failures += 1;
+                    backoff_ms = (backoff_ms * 2).min(RESTART_MAX_BACKOFF_MS);
+                    eprintln!(
+                        "[sidecar] Restart attempt {} failed: {}",
+                        consecutive_failures, e
+                    );
+
+                    if consecutive_failures >= RESTART_MAX_CONSECUTIVE_FAILURES {
+                        eprintln!(
+                            "[sidecar] Giving up after {} consecutive failed restarts",
+                            consecutive_failures
+                        );
+                        Self::emit_status(&app, "failed");
+                        return;
+                    }
+
+                    // The spawn itself failed so there's no process to wait
+                    // on; loop back around immediately to retry on the same
+                    // backoff schedule.
+                    let (tx, rx) = oneshot::channel();
+                    let _ = tx.send(());
+                    terminated_rx = rx;
+                }
+            }
+        }
     }
 
     /// Wait for server health endpoint to respond
@@ -177,4 +312,10 @@ impl SidecarState {
             HEALTH_CHECK_MAX_ATTEMPTS as u64 * HEALTH_CHECK_DELAY_MS
         ))
     }
+
+    fn emit_status(app: &AppHandle, status: &str) {
+        if let Err(e) = app.emit(SIDECAR_STATUS_EVENT, status) {
+            eprintln!("[sidecar] Failed to emit status event: {}", e);
+        }
+    }
 }